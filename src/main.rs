@@ -1,4 +1,7 @@
-use std::time::{Duration, Instant};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 use anyhow::{Context, Result};
 use clap::Parser;
@@ -37,6 +40,102 @@ struct Args {
         value_parser=humantime::parse_duration,
     )]
     interval: Duration,
+
+    /// Suppress per-pong log lines, only print the final summary.
+    #[clap(short, long)]
+    quiet: bool,
+}
+
+/// Accumulated ping statistics, filled in while the ping-pong loop runs.
+#[derive(Debug, Default)]
+struct PingStats {
+    sent: usize,
+    rtts: Vec<Duration>,
+}
+
+/// Sent/received/loss and RTT aggregates derived from a [`PingStats`].
+#[derive(Debug, PartialEq)]
+struct Summary {
+    sent: usize,
+    received: usize,
+    loss_pct: f64,
+    rtt: Option<RttSummary>,
+}
+
+/// min/avg/max/stddev over the RTT samples. `None` when no pongs were received.
+#[derive(Debug, PartialEq)]
+struct RttSummary {
+    min: Duration,
+    avg: Duration,
+    max: Duration,
+    stddev: Duration,
+}
+
+/// Compute sent/received/loss and RTT aggregates from `stats`. Pure, so it's testable without a
+/// live connection.
+fn summarize(stats: &PingStats) -> Summary {
+    let sent = stats.sent;
+    let received = stats.rtts.len();
+    let loss_pct = if sent == 0 {
+        0.0
+    } else {
+        100.0 * (sent - received) as f64 / sent as f64
+    };
+
+    let rtt = (!stats.rtts.is_empty()).then(|| {
+        let min = *stats.rtts.iter().min().expect("checked non-empty above");
+        let max = *stats.rtts.iter().max().expect("checked non-empty above");
+
+        let avg_nanos =
+            stats.rtts.iter().map(|d| d.as_nanos() as f64).sum::<f64>() / (received as f64);
+        let variance = stats
+            .rtts
+            .iter()
+            .map(|d| {
+                let diff = d.as_nanos() as f64 - avg_nanos;
+                diff * diff
+            })
+            .sum::<f64>()
+            / (received as f64);
+
+        RttSummary {
+            min,
+            max,
+            avg: Duration::from_nanos(avg_nanos as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    });
+
+    Summary {
+        sent,
+        received,
+        loss_pct,
+        rtt,
+    }
+}
+
+/// Print an ICMP-ping-style summary (sent/received/loss, min/avg/max/stddev RTT).
+fn print_summary(stats: &PingStats) {
+    let summary = summarize(stats);
+
+    match summary.rtt {
+        None => info!(
+            sent = summary.sent,
+            received = summary.received,
+            loss_pct = summary.loss_pct,
+            "ping statistics: no successful pings",
+        ),
+        Some(rtt) => info!(
+            sent = summary.sent,
+            received = summary.received,
+            loss_pct = summary.loss_pct,
+            min = ?rtt.min,
+            avg = ?rtt.avg,
+            max = ?rtt.max,
+            stddev = ?rtt.stddev,
+            "ping statistics",
+        ),
+    }
 }
 
 /// Main entry point.
@@ -67,14 +166,20 @@ async fn main() -> Result<()> {
 
     // set up ping-pong loop
     let count = args.count.unwrap_or(usize::MAX);
+    let stats = Mutex::new(PingStats::default());
+    let quiet = args.quiet;
     let looper = async {
         for _ in 0..count {
+            stats.lock().expect("stats mutex poisoned").sent += 1;
+
             let t_start = Instant::now();
             ping_pong.ping(Ping::opaque()).await.context("ping pong")?;
-            info!(
-                d=?t_start.elapsed(),
-                "pong",
-            );
+            let rtt = t_start.elapsed();
+            stats.lock().expect("stats mutex poisoned").rtts.push(rtt);
+
+            if !quiet {
+                info!(d = ?rtt, "pong");
+            }
 
             tokio::time::sleep(args.interval).await;
         }
@@ -87,11 +192,16 @@ async fn main() -> Result<()> {
     tokio::pin!(looper);
     futures::select! {
         _ = looper => {},
+        _ = tokio::signal::ctrl_c().fuse() => {
+            debug!("received Ctrl-C");
+        },
         e = driver_handle => {
             return Err(e.unwrap_err().into());
         }
     }
 
+    print_summary(&stats.lock().expect("stats mutex poisoned"));
+
     // shutdown
     cancel.cancel();
     driver_handle.await??;
@@ -99,3 +209,72 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarize_reports_no_loss_on_full_success() {
+        let stats = PingStats {
+            sent: 3,
+            rtts: vec![
+                Duration::from_millis(10),
+                Duration::from_millis(20),
+                Duration::from_millis(30),
+            ],
+        };
+
+        let summary = summarize(&stats);
+
+        assert_eq!(summary.sent, 3);
+        assert_eq!(summary.received, 3);
+        assert_eq!(summary.loss_pct, 0.0);
+
+        let rtt = summary.rtt.expect("rtt summary present");
+        assert_eq!(rtt.min, Duration::from_millis(10));
+        assert_eq!(rtt.max, Duration::from_millis(30));
+        assert_eq!(rtt.avg, Duration::from_millis(20));
+        assert_eq!(rtt.stddev, Duration::from_nanos(8164965));
+    }
+
+    #[test]
+    fn summarize_reports_partial_loss() {
+        let stats = PingStats {
+            sent: 4,
+            rtts: vec![Duration::from_millis(10), Duration::from_millis(20)],
+        };
+
+        let summary = summarize(&stats);
+
+        assert_eq!(summary.received, 2);
+        assert_eq!(summary.loss_pct, 50.0);
+        assert!(summary.rtt.is_some());
+    }
+
+    #[test]
+    fn summarize_handles_no_successful_pings() {
+        let stats = PingStats {
+            sent: 2,
+            rtts: Vec::new(),
+        };
+
+        let summary = summarize(&stats);
+
+        assert_eq!(summary.received, 0);
+        assert_eq!(summary.loss_pct, 100.0);
+        assert!(summary.rtt.is_none());
+    }
+
+    #[test]
+    fn summarize_handles_nothing_sent() {
+        let stats = PingStats::default();
+
+        let summary = summarize(&stats);
+
+        assert_eq!(summary.sent, 0);
+        assert_eq!(summary.received, 0);
+        assert_eq!(summary.loss_pct, 0.0);
+        assert!(summary.rtt.is_none());
+    }
+}